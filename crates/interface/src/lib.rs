@@ -21,7 +21,16 @@ wasmtime_integration!({
 pub struct GlamCtx {}
 
 #[derive(Debug)]
-pub enum InterfaceError {}
+pub enum InterfaceError {
+    Guest(GuestError),
+    InvalidArgument,
+}
+
+impl From<GuestError> for InterfaceError {
+    fn from(e: GuestError) -> Self {
+        InterfaceError::Guest(e)
+    }
+}
 
 impl GuestErrorType for types::Errno {
     fn success() -> Self {
@@ -30,8 +39,12 @@ impl GuestErrorType for types::Errno {
 }
 
 impl types::UserErrorConversion for GlamCtx {
-    fn errno_from_interface_error(&self, _: InterfaceError)-> Result<types::Errno, wiggle::Trap>  {
-        Ok(types::Errno::Ok)
+    fn errno_from_interface_error(&self, e: InterfaceError)-> Result<types::Errno, wiggle::Trap>  {
+        match e {
+            // Guest handed us a bad pointer, nothing sensible to report back through it
+            InterfaceError::Guest(e) => Err(wiggle::Trap::String(e.to_string())),
+            InterfaceError::InvalidArgument => Ok(types::Errno::InvalidArgument),
+        }
     }
 }
 
@@ -46,24 +59,91 @@ impl wasm_glam::WasmGlam for GlamCtx {
     }
 
     fn normalize(&self, in_: &types::Vec3) -> Result<types::Vec3, InterfaceError>  {
-        let v: glam::Vec3 = in_.into();
-        Ok(v.normalize().into())
+        try_normalize(in_.into()).map(Into::into)
+    }
+
+    fn normalize_batch(&self, vs: &types::Vec3List<'_>) -> Result<(), InterfaceError> {
+        // Normalizes in place so a guest can hand over a whole vertex buffer in one call.
+        // Every vector is checked before any are written so a failed call leaves the buffer untouched.
+        let normalized = vs.iter()
+            .map(|v_ptr| try_normalize(v_ptr?.read()?.into()))
+            .collect::<Result<Vec<_>, _>>()?;
+        for (v_ptr, v) in vs.iter().zip(normalized) {
+            v_ptr?.write(v.into())?;
+        }
+        Ok(())
+    }
+
+    fn add(&self, a: &types::Vec3, b: &types::Vec3) -> Result<types::Vec3, InterfaceError> {
+        let a: glam::Vec3 = a.into();
+        let b: glam::Vec3 = b.into();
+        Ok((a + b).into())
+    }
+
+    fn sub(&self, a: &types::Vec3, b: &types::Vec3) -> Result<types::Vec3, InterfaceError> {
+        let a: glam::Vec3 = a.into();
+        let b: glam::Vec3 = b.into();
+        Ok((a - b).into())
+    }
+
+    fn scale(&self, v: &types::Vec3, s: f32) -> Result<types::Vec3, InterfaceError> {
+        let v: glam::Vec3 = v.into();
+        Ok((v * s).into())
     }
     
     fn mul_vec3(&self, q: &types::Quat, v: &types::Vec3) -> Result<types::Vec3, InterfaceError>  {
         let q: glam::Quat = q.into();
         Ok(q.mul_vec3(v.into()).into())
     }
+
+    fn slerp(&self, a: &types::Quat, b: &types::Quat, t: f32) -> Result<types::Quat, InterfaceError> {
+        // glam only debug asserts unit inputs, normalize here so drift from chained rotations is tolerated
+        let a = try_normalize_quat(a.into())?;
+        let mut b = try_normalize_quat(b.into())?;
+        if !t.is_finite() {
+            return Err(InterfaceError::InvalidArgument);
+        }
+        // glam follows whichever arc the inputs describe, flip b into a's hemisphere so we always take the short one
+        if a.dot(b) < 0.0 {
+            b = -b;
+        }
+        // glam's slerp drifts off unit length by ~1e-4, hand guests back a proper rotation
+        Ok(a.slerp(b, t).normalize().into())
+    }
+
+    fn from_euler(&self, x: f32, y: f32, z: f32) -> Result<types::Quat, InterfaceError> {
+        // glam names the angles yaw (y), pitch (x) and roll (z)
+        Ok(glam::Quat::from_rotation_ypr(y, x, z).into())
+    }
+}
+
+// glam happily normalizes zero or infinite vectors into NaNs, report them to the guest instead
+fn try_normalize(v: glam::Vec3) -> Result<glam::Vec3, InterfaceError> {
+    let len_sq = v.length_squared();
+    // A non-finite length covers NaN and infinite components, as well as finite ones large enough to overflow
+    if !len_sq.is_finite() || len_sq <= f32::EPSILON * f32::EPSILON {
+        return Err(InterfaceError::InvalidArgument);
+    }
+    Ok(v.normalize())
+}
+
+// Same checks as try_normalize, for quats handed to us by the guest
+fn try_normalize_quat(q: glam::Quat) -> Result<glam::Quat, InterfaceError> {
+    let len_sq = q.length_squared();
+    if !len_sq.is_finite() || len_sq <= f32::EPSILON * f32::EPSILON {
+        return Err(InterfaceError::InvalidArgument);
+    }
+    Ok(q.normalize())
 }
 
-impl Into<glam::Vec3> for &types::Vec3 {
-    fn into(self) -> glam::Vec3 {
-       glam::Vec3::new(self.x, self.y, self.z) 
+impl From<&types::Vec3> for glam::Vec3 {
+    fn from(v: &types::Vec3) -> Self {
+       glam::Vec3::new(v.x, v.y, v.z)
     }
 }
-impl Into<glam::Vec3> for types::Vec3 {
-    fn into(self) -> glam::Vec3 {
-        glam::Vec3::new(self.x, self.y, self.z)
+impl From<types::Vec3> for glam::Vec3 {
+    fn from(v: types::Vec3) -> Self {
+        glam::Vec3::new(v.x, v.y, v.z)
     }
 }
 impl From<glam::Vec3> for types::Vec3 {
@@ -76,13 +156,283 @@ impl From<glam::Vec3> for types::Vec3 {
     }
 }
 
-impl Into<glam::Quat> for &types::Quat {
-    fn into(self) -> glam::Quat {
-        glam::Quat::from_xyzw(self.x, self.y, self.z, self.w)
+impl From<&types::Quat> for glam::Quat {
+    fn from(q: &types::Quat) -> Self {
+        glam::Quat::from_xyzw(q.x, q.y, q.z, q.w)
     }
 }
-impl Into<glam::Quat> for types::Quat {
-    fn into(self) -> glam::Quat {
-        glam::Quat::from_xyzw(self.x, self.y, self.z, self.w)
+impl From<types::Quat> for glam::Quat {
+    fn from(q: types::Quat) -> Self {
+        glam::Quat::from_xyzw(q.x, q.y, q.z, q.w)
+    }
+}
+impl From<glam::Quat> for types::Quat {
+    fn from(q: glam::Quat) -> Self {
+        types::Quat {
+            x: q.x,
+            y: q.y,
+            z: q.z,
+            w: q.w,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::types::UserErrorConversion as _;
+    use super::wasm_glam::WasmGlam as _;
+
+    // A single 64KiB page of wasm memory for the guest pointers to live in
+    fn guest_memory() -> WasmtimeGuestMemory {
+        let store = wasmtime::Store::default();
+        let mem = wasmtime::Memory::new(&store, wasmtime::MemoryType::new(wasmtime::Limits::new(1, None)));
+        WasmtimeGuestMemory::new(mem)
+    }
+
+    fn write_vec3s(list: &types::Vec3List<'_>, vs: &[glam::Vec3]) {
+        for (v_ptr, v) in list.iter().zip(vs) {
+            v_ptr.unwrap().write((*v).into()).unwrap();
+        }
+    }
+
+    fn read_vec3s(list: &types::Vec3List<'_>) -> Vec<glam::Vec3> {
+        list.iter()
+            .map(|v_ptr| v_ptr.unwrap().read().unwrap().into())
+            .collect()
+    }
+
+    #[test]
+    fn normalize_batch_normalizes_guest_vectors_in_place() {
+        let mem = guest_memory();
+        let vs = [
+            glam::Vec3::new(3.0, 0.0, 0.0),
+            glam::Vec3::new(1.0, 2.0, 3.0),
+            glam::Vec3::new(-4.0, 0.5, 8.0),
+            glam::Vec3::new(0.0, -0.25, 0.0),
+        ];
+        let list: types::Vec3List<'_> = GuestPtr::new(&mem, (16, vs.len() as u32));
+        write_vec3s(&list, &vs);
+
+        GlamCtx {}.normalize_batch(&list).unwrap();
+
+        let expected: Vec<glam::Vec3> = vs.iter().map(|v| v.normalize()).collect();
+        assert_eq!(read_vec3s(&list), expected);
+    }
+
+    #[test]
+    fn normalize_batch_out_of_bounds_traps() {
+        let mem = guest_memory();
+        // Starts in bounds but runs off the end of the page
+        let list: types::Vec3List<'_> = GuestPtr::new(&mem, (65536 - 16, 4));
+        // Make the one in-bounds vector valid so the failure is the pointer rather than its contents
+        GuestPtr::<types::Vec3>::new(&mem, 65536 - 16).write(glam::Vec3::X.into()).unwrap();
+
+        let ctx = GlamCtx {};
+        let err = ctx.normalize_batch(&list).unwrap_err();
+        assert!(matches!(err, InterfaceError::Guest(_)));
+        assert!(matches!(ctx.errno_from_interface_error(err), Err(wiggle::Trap::String(_))));
+    }
+
+    fn degenerate_vec3s() -> Vec<glam::Vec3> {
+        vec![
+            glam::Vec3::ZERO,
+            glam::Vec3::new(1e-8, 0.0, -1e-8),
+            glam::Vec3::new(f32::NAN, 1.0, 0.0),
+            glam::Vec3::new(f32::INFINITY, 0.0, 0.0),
+            glam::Vec3::new(0.0, f32::NEG_INFINITY, 1.0),
+            // Finite, but its squared length overflows
+            glam::Vec3::new(1e20, 0.0, 0.0),
+        ]
+    }
+
+    #[test]
+    fn normalize_rejects_degenerate_vectors() {
+        let ctx = GlamCtx {};
+        for v in degenerate_vec3s() {
+            let res = ctx.normalize(&v.into());
+            assert!(matches!(res, Err(InterfaceError::InvalidArgument)), "{:?} should be rejected", v);
+        }
+    }
+
+    #[test]
+    fn normalize_accepts_ordinary_vectors() {
+        for v in [glam::Vec3::new(1.0, -2.0, 2.0), glam::Vec3::new(1e19, 0.0, 0.0)].iter() {
+            let res: glam::Vec3 = GlamCtx {}.normalize(&(*v).into()).unwrap().into();
+            assert_eq!(res, v.normalize());
+        }
+    }
+
+    #[test]
+    fn invalid_argument_maps_to_errno() {
+        let errno = GlamCtx {}.errno_from_interface_error(InterfaceError::InvalidArgument);
+        assert!(matches!(errno, Ok(types::Errno::InvalidArgument)));
+    }
+
+    fn assert_quat_eq(actual: glam::Quat, expected: glam::Quat) {
+        assert!(actual.abs_diff_eq(expected, 1e-6), "{:?} != {:?}", actual, expected);
+    }
+
+    #[test]
+    fn quat_conversions_round_trip() {
+        let q = glam::Quat::from_xyzw(0.1, -0.2, 0.3, 0.9);
+        let t: types::Quat = q.into();
+        let by_ref: glam::Quat = (&t).into();
+        let by_val: glam::Quat = t.into();
+        assert_eq!(by_ref, q);
+        assert_eq!(by_val, q);
+    }
+
+    #[test]
+    fn slerp_returns_endpoints() {
+        let a = glam::Quat::from_rotation_y(0.3);
+        let b = glam::Quat::from_rotation_ypr(1.0, 0.5, -0.25);
+        let ctx = GlamCtx {};
+
+        let start: glam::Quat = ctx.slerp(&a.into(), &b.into(), 0.0).unwrap().into();
+        let end: glam::Quat = ctx.slerp(&a.into(), &b.into(), 1.0).unwrap().into();
+        assert_quat_eq(start, a);
+        assert_quat_eq(end, b);
+    }
+
+    #[test]
+    fn slerp_takes_shortest_arc() {
+        let a = glam::Quat::from_rotation_y(0.3);
+        let b = glam::Quat::from_rotation_y(1.2);
+        let ctx = GlamCtx {};
+
+        // -b is the same rotation as b, so both should interpolate identically
+        let via_b: glam::Quat = ctx.slerp(&a.into(), &b.into(), 0.5).unwrap().into();
+        let via_neg_b: glam::Quat = ctx.slerp(&a.into(), &(-b).into(), 0.5).unwrap().into();
+        assert_quat_eq(via_neg_b, via_b);
+        assert_quat_eq(via_b, glam::Quat::from_rotation_y(0.75));
+
+        // Antipodal inputs are the same rotation and must not divide by sin(pi)
+        let same: glam::Quat = ctx.slerp(&a.into(), &(-a).into(), 0.5).unwrap().into();
+        assert_quat_eq(same, a);
+    }
+
+    #[test]
+    fn slerp_normalizes_inputs() {
+        let ctx = GlamCtx {};
+        let a = glam::Quat::from_rotation_y(0.3);
+        let b = glam::Quat::from_rotation_y(1.2);
+
+        // Scaled quats describe the same rotations as their unit counterparts
+        let scaled: glam::Quat = ctx.slerp(&(a * 2.0).into(), &(b * 0.5).into(), 0.5).unwrap().into();
+        assert_quat_eq(scaled, glam::Quat::from_rotation_y(0.75));
+
+        // Chaining per-frame rotations drifts off unit length, further than glam's is_normalized allows
+        let delta = glam::Quat::from_rotation_ypr(0.02, -0.007, 0.021);
+        let drifted = (0..60).fold(a, |q, _| q * delta);
+        assert!(!drifted.is_normalized());
+        let res: glam::Quat = ctx.slerp(&drifted.into(), &b.into(), 0.0).unwrap().into();
+        assert_quat_eq(res, drifted.normalize());
+    }
+
+    #[test]
+    fn slerp_rejects_degenerate_quats() {
+        let unit = glam::Quat::IDENTITY;
+        let ctx = GlamCtx {};
+        for bad in [
+            glam::Quat::from_xyzw(0.0, 0.0, 0.0, 0.0),
+            glam::Quat::from_xyzw(f32::NAN, 0.0, 0.0, 1.0),
+            glam::Quat::from_xyzw(0.0, f32::INFINITY, 0.0, 1.0),
+        ].iter() {
+            let res = ctx.slerp(&unit.into(), &(*bad).into(), 0.5);
+            assert!(matches!(res, Err(InterfaceError::InvalidArgument)), "{:?} should be rejected", bad);
+            let res = ctx.slerp(&(*bad).into(), &unit.into(), 0.5);
+            assert!(matches!(res, Err(InterfaceError::InvalidArgument)), "{:?} should be rejected", bad);
+        }
+    }
+
+    #[test]
+    fn slerp_rejects_non_finite_t() {
+        let a = glam::Quat::from_rotation_y(0.3);
+        let b = glam::Quat::from_rotation_y(1.2);
+        let ctx = GlamCtx {};
+        for t in [f32::NAN, f32::INFINITY, f32::NEG_INFINITY].iter() {
+            let res = ctx.slerp(&a.into(), &b.into(), *t);
+            assert!(matches!(res, Err(InterfaceError::InvalidArgument)), "t = {} should be rejected", t);
+        }
+    }
+
+    #[test]
+    fn from_euler_applies_yaw_pitch_roll() {
+        let (x, y, z) = (0.4, -1.1, 2.3);
+        let q: glam::Quat = GlamCtx {}.from_euler(x, y, z).unwrap().into();
+        let expected = glam::Quat::from_rotation_y(y) * glam::Quat::from_rotation_x(x) * glam::Quat::from_rotation_z(z);
+        assert_quat_eq(q, expected);
+    }
+
+    #[test]
+    fn vec3_arithmetic_matches_glam() {
+        let a = glam::Vec3::new(1.5, -2.0, 4.0);
+        let b = glam::Vec3::new(-0.5, 3.0, 0.25);
+        let ctx = GlamCtx {};
+
+        let sum: glam::Vec3 = ctx.add(&a.into(), &b.into()).unwrap().into();
+        let diff: glam::Vec3 = ctx.sub(&a.into(), &b.into()).unwrap().into();
+        let scaled: glam::Vec3 = ctx.scale(&a.into(), -3.0).unwrap().into();
+        assert_eq!(sum, a + b);
+        assert_eq!(diff, a - b);
+        assert_eq!(scaled, a * -3.0);
+    }
+
+    #[test]
+    fn vec3_arithmetic_passes_through_nan_and_inf() {
+        let ctx = GlamCtx {};
+        let inf = glam::Vec3::new(f32::INFINITY, 1.0, 1.0);
+        let neg_inf = glam::Vec3::new(f32::NEG_INFINITY, 1.0, 1.0);
+        let nan = glam::Vec3::new(f32::NAN, 1.0, 1.0);
+        let one = glam::Vec3::ONE;
+
+        let sum: glam::Vec3 = ctx.add(&inf.into(), &one.into()).unwrap().into();
+        assert_eq!(sum, glam::Vec3::new(f32::INFINITY, 2.0, 2.0));
+
+        let sum: glam::Vec3 = ctx.add(&inf.into(), &neg_inf.into()).unwrap().into();
+        assert!(sum.x.is_nan());
+        assert_eq!((sum.y, sum.z), (2.0, 2.0));
+
+        let diff: glam::Vec3 = ctx.sub(&inf.into(), &inf.into()).unwrap().into();
+        assert!(diff.x.is_nan());
+
+        let diff: glam::Vec3 = ctx.sub(&neg_inf.into(), &one.into()).unwrap().into();
+        assert_eq!(diff.x, f32::NEG_INFINITY);
+
+        let sum: glam::Vec3 = ctx.add(&nan.into(), &one.into()).unwrap().into();
+        assert!(sum.x.is_nan());
+        assert_eq!((sum.y, sum.z), (2.0, 2.0));
+
+        let scaled: glam::Vec3 = ctx.scale(&inf.into(), 0.0).unwrap().into();
+        assert!(scaled.x.is_nan());
+        assert_eq!((scaled.y, scaled.z), (0.0, 0.0));
+
+        let scaled: glam::Vec3 = ctx.scale(&one.into(), f32::INFINITY).unwrap().into();
+        assert_eq!(scaled, glam::Vec3::new(f32::INFINITY, f32::INFINITY, f32::INFINITY));
+
+        let scaled: glam::Vec3 = ctx.scale(&one.into(), f32::NAN).unwrap().into();
+        assert!(scaled.is_nan_mask().all());
+    }
+
+    #[test]
+    fn normalize_batch_rejects_degenerate_vectors_without_writing() {
+        let mem = guest_memory();
+        let ctx = GlamCtx {};
+        for bad in degenerate_vec3s() {
+            // Put the bad vector last so every vector before it would have been written already
+            let vs = [glam::Vec3::new(3.0, 0.0, 0.0), glam::Vec3::new(0.0, 4.0, 3.0), bad];
+            let list: types::Vec3List<'_> = GuestPtr::new(&mem, (16, vs.len() as u32));
+            write_vec3s(&list, &vs);
+
+            let res = ctx.normalize_batch(&list);
+            assert!(matches!(res, Err(InterfaceError::InvalidArgument)), "{:?} should be rejected", bad);
+
+            let after = read_vec3s(&list);
+            assert_eq!(after[..2], vs[..2]);
+            // NaN never compares equal, compare bit patterns instead
+            let bits = |v: glam::Vec3| [v.x.to_bits(), v.y.to_bits(), v.z.to_bits()];
+            assert_eq!(bits(after[2]), bits(bad));
+        }
     }
 }
\ No newline at end of file